@@ -0,0 +1,89 @@
+use {
+  crate::{
+    log::{Err, LogFormat},
+    server::response::Host,
+    types::Result,
+  },
+  serde::Deserialize,
+  std::fs,
+};
+
+/// Runtime configuration, loaded once at startup from a TOML file.
+///
+/// This replaces what used to be a handful of compile-time `consts` (the
+/// bind address, the YAML data file, the log file, and the domain/`Host`
+/// mapping), so the same binary can serve different sites on different
+/// ports without a rebuild.
+#[derive(Deserialize)]
+pub struct Config {
+  pub bind_addr: String,
+  /// Must deserialize straight into `CatInfo`/`GenInfo`/`SpecInfo` via serde
+  /// now that the hand-rolled indentation parser is gone — check the file
+  /// at this path against that shape after upgrading.
+  pub yaml_file: String,
+  pub log_file: String,
+  #[serde(default)]
+  pub log_format: LogFormat,
+  /// Rotate the log file once it exceeds this many bytes. `None` (the
+  /// default) disables rotation entirely.
+  #[serde(default)]
+  pub log_rotate_bytes: Option<u64>,
+  #[serde(rename = "hosts", default)]
+  pub hosts: Vec<HostEntry>,
+}
+
+#[derive(Deserialize)]
+pub struct HostEntry {
+  pub domain: String,
+  pub kind: HostKind,
+}
+
+/// Mirrors `server::response::Host`, but lives here so the mapping can be
+/// deserialized straight out of the `[[hosts]]` table.
+#[derive(Deserialize)]
+pub enum HostKind {
+  Mycology,
+  Site,
+}
+
+impl HostKind {
+  fn to_host(&self) -> Host {
+    match self {
+      HostKind::Mycology => Host::Mycology,
+      HostKind::Site => Host::Site,
+    }
+  }
+}
+
+impl Config {
+  /// Looks up which `Host` a request's `Host:` header domain maps to,
+  /// per the `[[hosts]]` table.
+  pub fn host_for_domain(&self, domain: &str) -> Option<Host> {
+    self
+      .hosts
+      .iter()
+      .find(|entry| entry.domain == domain)
+      .map(|entry| entry.kind.to_host())
+  }
+}
+
+/// Loads and parses the TOML config at `path`. Any failure is logged via
+/// `log_err` and returned to the caller, who decides whether to bail out
+/// of startup entirely.
+pub fn load(path: &str) -> Result<Config> {
+  let raw = match fs::read_to_string(path) {
+    Ok(v) => v,
+    Err(e) => {
+      format!("config read error. :( - {} {}", e, path).log_err();
+      return Err(Box::new(e));
+    }
+  };
+
+  match toml::from_str(&raw) {
+    Ok(v) => Ok(v),
+    Err(e) => {
+      format!("config parse error. :( - {} {}", e, path).log_err();
+      Err(Box::new(e))
+    }
+  }
+}
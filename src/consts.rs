@@ -0,0 +1,28 @@
+/// Fallback log path, used only before `log::set_log_path` has been called
+/// with the configured one (e.g. if loading the config itself fails).
+pub const LOG_FILE: &str = "server.log";
+
+pub struct Paths {
+  pub menu: &'static str,
+  pub frag_menu: &'static str,
+  pub shroompage: &'static str,
+  pub site_root: &'static str,
+}
+
+pub static PATH: Paths = Paths {
+  menu: "templates/menu.html",
+  frag_menu: "templates/frag_menu.html",
+  shroompage: "templates/shroompage.html",
+  site_root: "static",
+};
+
+pub mod status {
+  pub const HTTP_200: &str = "HTTP/1.1 200 OK";
+  pub const HTTP_304: &str = "HTTP/1.1 304 Not Modified";
+  pub const HTTP_404: &str = "HTTP/1.1 404 Not Found";
+}
+
+pub mod domains {
+  pub const MYCOLOGY: &str = "mycology";
+  pub const NO_DOMAIN: &str = "site";
+}
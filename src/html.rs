@@ -0,0 +1,42 @@
+use {
+  crate::{
+    mycology::generate::CatInfo,
+    types::{Categories, Result},
+  },
+  std::fs,
+};
+
+pub fn from_file(path: &str) -> Result<String> {
+  Ok(fs::read_to_string(path)?)
+}
+
+pub fn menu(categories: &Categories, html_frag: &str) -> String {
+  categories
+    .iter()
+    .map(|cat| {
+      html_frag
+        .replace("{LABEL}", &cat.label)
+        .replace("{TITLE}", &cat.title)
+    })
+    .collect()
+}
+
+impl CatInfo {
+  pub fn htmlify(&self) -> Result<String> {
+    Ok(
+      self
+        .genera
+        .iter()
+        .map(|genus| {
+          let species = genus
+            .species
+            .iter()
+            .map(|sp| format!("<h3>{} ({})</h3><p>{}</p>", sp.title, sp.name, sp.blurb))
+            .collect::<String>();
+
+          format!("<h2>{}</h2>{}", genus.title, species)
+        })
+        .collect(),
+    )
+  }
+}
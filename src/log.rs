@@ -4,6 +4,7 @@ use {
     server::response::Host,
     types::IpAddr,
   },
+  serde::{Deserialize, Serialize},
   std::{
     fmt, fs,
     io::Write,
@@ -25,6 +26,16 @@ pub struct Log {
   pub start_time: time::SystemTime,
 }
 
+/// Which shape `logger` writes each entry in. `Json` is meant for
+/// downstream log-ingestion tooling; `Text` is the original human-readable
+/// `mini_log`/`big_log` format.
+#[derive(Deserialize, Default, Copy, Clone)]
+pub enum LogFormat {
+  #[default]
+  Text,
+  Json,
+}
+
 pub trait Err {
   fn log_err(self);
 }
@@ -34,22 +45,26 @@ where
   T: fmt::Debug,
 {
   fn log_err(self) {
-    // TODO: this should be async, and should pass the log_file into here
+    // TODO: this should be async
     // use OnceLock to initialize only once, then place globally
     // use Mutex for interior mutability
     // use Option because we might fail doing this
     static OPENLOGFILE: std::sync::OnceLock<Option<Mutex<fs::File>>> = std::sync::OnceLock::new();
 
+    // set once at startup via `set_log_path`, from the loaded `Config`;
+    // falls back to `consts::LOG_FILE` for errors logged before that (e.g.
+    // a failure to load the config itself)
+    let path = LOG_PATH
+      .get()
+      .map(String::as_str)
+      .unwrap_or(consts::LOG_FILE);
+
     // this initializes only once, gets
     let log_file = OPENLOGFILE.get_or_init(|| {
-      match fs::OpenOptions::new()
-        .append(true)
-        .create(true)
-        .open(consts::LOG_FILE)
-      {
+      match fs::OpenOptions::new().append(true).create(true).open(path) {
         // unfortunately, this method tries only once
         Err(e) => {
-          eprintln!("{} {} - cannot open log file", e, consts::LOG_FILE);
+          eprintln!("{} {} - cannot open log file", e, path);
           None
         },
 
@@ -60,17 +75,26 @@ where
     if let Some(f) = log_file.as_ref() {
       if let Err(e) = {
         let mut lock = f.lock().unwrap();
-        
+
         // don't allocate a new string using format! when we can write directly
         // to disk instead
         write!(&mut *lock, "ERROR - {:?}\n", self)
       } {
-        eprintln!("{} {} - error writing to log file", e, consts::LOG_FILE);
+        eprintln!("{} {} - error writing to log file", e, path);
       }
     }
   }
 }
 
+static LOG_PATH: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+
+/// Points `log_err` at the configured log file. Call once at startup, as
+/// soon as the `Config` is loaded; only the first call sets the path, and
+/// it has no effect if `log_err` has already opened the fallback file.
+pub fn set_log_path(path: String) {
+  let _ = LOG_PATH.set(path);
+}
+
 trait ToString {
   fn to_string(self) -> String;
 }
@@ -151,13 +175,117 @@ impl ToWdhms for u64 {
   }
 }
 
-pub async fn logger(mut receiver: UnboundedReceiver<Log>) {
+#[derive(Serialize)]
+struct JsonLogLine<'a> {
+  timestamp: String,
+  ip: String,
+  host: String,
+  path: &'a str,
+  referer: &'a str,
+  user_agent: &'a str,
+  status: &'a str,
+  length: usize,
+  turnaround_micros: u128,
+  total_conn: i32,
+  unique_conn: i32,
+}
+
+/// Renames the log file to a timestamped archive name and reopens a fresh
+/// one, if it has grown past `limit` bytes. Does nothing when it hasn't,
+/// or when the file's size can't be read. A failed rename is reported and
+/// keeps the current file; a failed reopen leaves the error in `file`.
+fn rotate_if_needed(file: &mut std::io::Result<fs::File>, log_file: &str, limit: u64) {
+  let len = match file.as_mut().ok().and_then(|f| f.metadata().ok()) {
+    Some(m) => m.len(),
+    None => return,
+  };
+
+  if len <= limit {
+    return;
+  }
+
+  let archive = format!(
+    "{log_file}.{}",
+    humantime::format_rfc3339_seconds(time::SystemTime::now())
+      .to_string()
+      .replace(':', "-")
+  );
+
+  if let Err(e) = fs::rename(log_file, &archive) {
+    eprintln!("{} {} - cannot rotate log file", e, log_file);
+    return;
+  }
+
+  *file = fs::OpenOptions::new()
+    .append(true)
+    .create(true)
+    .open(log_file);
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn temp_log_path(name: &str) -> String {
+    std::env::temp_dir()
+      .join(format!("rusty_website_test_{}_{name}.log", std::process::id()))
+      .to_string_lossy()
+      .into_owned()
+  }
+
+  #[test]
+  fn leaves_the_file_alone_under_the_limit() {
+    let path = temp_log_path("under_limit");
+    fs::write(&path, b"short").unwrap();
+    let mut file = fs::OpenOptions::new().append(true).open(&path);
+
+    rotate_if_needed(&mut file, &path, 1024);
+
+    assert_eq!(fs::metadata(&path).unwrap().len(), 5);
+    fs::remove_file(&path).unwrap();
+  }
+
+  #[test]
+  fn rotates_to_an_archive_once_past_the_limit() {
+    let path = temp_log_path("over_limit");
+    fs::write(&path, vec![b'x'; 10]).unwrap();
+    let mut file = fs::OpenOptions::new().append(true).open(&path);
+
+    rotate_if_needed(&mut file, &path, 5);
+
+    // the original path now holds a fresh, empty file...
+    assert_eq!(fs::metadata(&path).unwrap().len(), 0);
+
+    // ...and the old content moved to a timestamped archive alongside it.
+    let archive_prefix = format!("{path}.");
+    let dir = std::path::Path::new(&path).parent().unwrap();
+    let archive = fs::read_dir(dir)
+      .unwrap()
+      .filter_map(|e| e.ok())
+      .find(|e| e.path().to_string_lossy().starts_with(&archive_prefix));
+
+    assert!(archive.is_some());
+
+    fs::remove_file(&path).ok();
+    if let Some(entry) = archive {
+      fs::remove_file(entry.path()).ok();
+    }
+  }
+}
+
+pub async fn logger(
+  mut receiver: UnboundedReceiver<Log>,
+  log_file: String,
+  format: LogFormat,
+  rotate_bytes: Option<u64>,
+  metrics: std::sync::Arc<crate::metrics::Metrics>,
+) {
   let none = || "None".to_owned();
 
   let mut file = fs::OpenOptions::new()
     .append(true)
     .create(true)
-    .open(consts::LOG_FILE);
+    .open(&log_file);
 
   let mut prev_ip = None::<IpAddr>;
   let mut total_conn = 0;
@@ -169,8 +297,12 @@ pub async fn logger(mut receiver: UnboundedReceiver<Log>) {
       Some(m) => m,
     };
 
+    if let Some(limit) = rotate_bytes {
+      rotate_if_needed(&mut file, &log_file, limit);
+    }
+
     match (message, file.as_mut()) {
-      (_, Err(e)) => eprintln!("{} {} - cannot open log file", e, consts::LOG_FILE),
+      (_, Err(e)) => eprintln!("{} {} - cannot open log file", e, log_file),
       (log, Ok(file)) => {
         let Log {
           path,
@@ -193,42 +325,75 @@ pub async fn logger(mut receiver: UnboundedReceiver<Log>) {
         let user_agent = user_agent.unwrap_or_else(none);
         let turnaround = cxn_time.to_elapsed();
 
-        let mini_log = |total_conn: i32| {
-          format!(
-        "#{total_conn} - {ip_str} - {timestamp} - {status} - {length}b - {turnaround} - {path}\n"
-      )
-        };
+        total_conn += 1;
+        let is_unique = prev_ip.unwrap_or_default() != ip.unwrap_or_default();
+        if is_unique {
+          unique_conn += 1;
+          metrics.record_unique_connection();
+        }
+        metrics.record(&status, length);
 
-        let big_log = |total_conn: i32, unique_conn: i32| {
-          format!(
-            "START\n\
-            Timestamp: {timestamp}\n\
-            # Unique: {unique_conn}\n\
-            # Total: {total_conn}\n\
-            Up-time:{uptime}\n\
-            Request:\n\
-            \tPath: {path}\n\
-            \tHost: {host}\n\
-            \tIp: {ip_str}\n\
-            \tReferer: {referer}\n\
-            \tAgent: {user_agent}\n\
-            Response:\n\
-            \tStatus: {status}\n\
-            \tLength: {length} bytes\n\
-            \tTurnaround: {turnaround}\n"
+        let string = match format {
+          LogFormat::Text => {
+            let mini_log = || {
+              format!(
+            "#{total_conn} - {ip_str} - {timestamp} - {status} - {length}b - {turnaround} - {path}\n"
           )
-        };
+            };
 
-        total_conn += 1;
-        let string = if prev_ip.unwrap_or_default() == ip.unwrap_or_default() {
-          mini_log(total_conn)
-        } else {
-          unique_conn += 1;
-          big_log(total_conn, unique_conn)
+            let big_log = || {
+              format!(
+                "START\n\
+                Timestamp: {timestamp}\n\
+                # Unique: {unique_conn}\n\
+                # Total: {total_conn}\n\
+                Up-time:{uptime}\n\
+                Request:\n\
+                \tPath: {path}\n\
+                \tHost: {host}\n\
+                \tIp: {ip_str}\n\
+                \tReferer: {referer}\n\
+                \tAgent: {user_agent}\n\
+                Response:\n\
+                \tStatus: {status}\n\
+                \tLength: {length} bytes\n\
+                \tTurnaround: {turnaround}\n"
+              )
+            };
+
+            if is_unique {
+              big_log()
+            } else {
+              mini_log()
+            }
+          }
+          LogFormat::Json => {
+            let line = JsonLogLine {
+              timestamp,
+              ip: ip_str,
+              host,
+              path: &path,
+              referer: &referer,
+              user_agent: &user_agent,
+              status: &status,
+              length,
+              turnaround_micros: cxn_time.elapsed().map(|d| d.as_micros()).unwrap_or(0),
+              total_conn,
+              unique_conn,
+            };
+
+            match serde_json::to_string(&line) {
+              Ok(v) => format!("{v}\n"),
+              Err(e) => {
+                eprintln!("{} - cannot serialize log entry", e);
+                continue;
+              }
+            }
+          }
         };
 
         if let Err(e) = file.write_all(string.as_bytes()) {
-          eprintln!("{} {} - error writing to log file", e, consts::LOG_FILE)
+          eprintln!("{} {} - error writing to log file", e, log_file)
         }
         print!("{string}");
         prev_ip = ip;
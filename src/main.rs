@@ -0,0 +1,14 @@
+mod config;
+mod consts;
+mod html;
+mod log;
+mod metrics;
+mod mycology;
+mod server;
+mod types;
+
+#[tokio::main]
+async fn main() -> types::Result<()> {
+  let config = config::load("config.toml")?;
+  server::run::start_server(config).await
+}
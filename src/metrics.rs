@@ -0,0 +1,128 @@
+use {
+  crate::{consts, server::response::Response},
+  std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time,
+  },
+};
+
+/// Counters fed by `logger` on every request, exposed to operators as a
+/// Prometheus scrape target at `/metrics` instead of being trapped inside
+/// the logging task.
+#[derive(Default)]
+pub struct Metrics {
+  total_requests: AtomicU64,
+  unique_connections: AtomicU64,
+  response_bytes: AtomicU64,
+  status_2xx: AtomicU64,
+  status_3xx: AtomicU64,
+  status_4xx: AtomicU64,
+  status_5xx: AtomicU64,
+}
+
+impl Metrics {
+  pub fn record(&self, status: &str, length: usize) {
+    self.total_requests.fetch_add(1, Ordering::Relaxed);
+    self
+      .response_bytes
+      .fetch_add(length as u64, Ordering::Relaxed);
+
+    let bucket = match status.trim().as_bytes().first() {
+      Some(b'2') => &self.status_2xx,
+      Some(b'3') => &self.status_3xx,
+      Some(b'4') => &self.status_4xx,
+      Some(b'5') => &self.status_5xx,
+      _ => return,
+    };
+    bucket.fetch_add(1, Ordering::Relaxed);
+  }
+
+  pub fn record_unique_connection(&self) {
+    self.unique_connections.fetch_add(1, Ordering::Relaxed);
+  }
+
+  fn render(&self, uptime_secs: u64) -> String {
+    format!(
+      "# HELP rusty_website_uptime_seconds Seconds since the server started.\n\
+      # TYPE rusty_website_uptime_seconds gauge\n\
+      rusty_website_uptime_seconds {uptime_secs}\n\
+      # HELP rusty_website_unique_connections Requests from a different client IP than the previous request.\n\
+      # TYPE rusty_website_unique_connections gauge\n\
+      rusty_website_unique_connections {unique}\n\
+      # HELP rusty_website_requests_total Total requests served.\n\
+      # TYPE rusty_website_requests_total counter\n\
+      rusty_website_requests_total {total}\n\
+      # HELP rusty_website_response_bytes_total Total response bytes served.\n\
+      # TYPE rusty_website_response_bytes_total counter\n\
+      rusty_website_response_bytes_total {bytes}\n\
+      # HELP rusty_website_responses_total Responses served, by status class.\n\
+      # TYPE rusty_website_responses_total counter\n\
+      rusty_website_responses_total{{class=\"2xx\"}} {c2}\n\
+      rusty_website_responses_total{{class=\"3xx\"}} {c3}\n\
+      rusty_website_responses_total{{class=\"4xx\"}} {c4}\n\
+      rusty_website_responses_total{{class=\"5xx\"}} {c5}\n",
+      unique = self.unique_connections.load(Ordering::Relaxed),
+      total = self.total_requests.load(Ordering::Relaxed),
+      bytes = self.response_bytes.load(Ordering::Relaxed),
+      c2 = self.status_2xx.load(Ordering::Relaxed),
+      c3 = self.status_3xx.load(Ordering::Relaxed),
+      c4 = self.status_4xx.load(Ordering::Relaxed),
+      c5 = self.status_5xx.load(Ordering::Relaxed),
+    )
+  }
+}
+
+/// Renders the current snapshot as a `Response`, for the `/metrics` route.
+pub fn get(metrics: &Metrics, uptime: time::SystemTime) -> Response {
+  let uptime_secs = uptime.elapsed().map(|d| d.as_secs()).unwrap_or(0);
+
+  Response {
+    status: consts::status::HTTP_200,
+    mime_type: "text/plain; version=0.0.4",
+    content: metrics.render(uptime_secs).into_bytes(),
+    last_modified: None,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn record_buckets_by_status_class() {
+    let metrics = Metrics::default();
+    metrics.record("200 OK", 10);
+    metrics.record("204 No Content", 0);
+    metrics.record("404 Not Found", 20);
+    metrics.record("503 Service Unavailable", 0);
+
+    let rendered = metrics.render(0);
+    assert!(rendered.contains("rusty_website_responses_total{class=\"2xx\"} 2\n"));
+    assert!(rendered.contains("rusty_website_responses_total{class=\"4xx\"} 1\n"));
+    assert!(rendered.contains("rusty_website_responses_total{class=\"5xx\"} 1\n"));
+    assert!(rendered.contains("rusty_website_responses_total{class=\"3xx\"} 0\n"));
+    assert!(rendered.contains("rusty_website_requests_total 4\n"));
+    assert!(rendered.contains("rusty_website_response_bytes_total 30\n"));
+  }
+
+  #[test]
+  fn record_ignores_an_unrecognized_status_class() {
+    let metrics = Metrics::default();
+    metrics.record("unknown", 5);
+
+    let rendered = metrics.render(0);
+    assert!(rendered.contains("rusty_website_requests_total 1\n"));
+    assert!(rendered.contains("rusty_website_responses_total{class=\"2xx\"} 0\n"));
+  }
+
+  #[test]
+  fn record_unique_connection_increments_the_gauge() {
+    let metrics = Metrics::default();
+    metrics.record_unique_connection();
+    metrics.record_unique_connection();
+
+    assert!(metrics
+      .render(0)
+      .contains("rusty_website_unique_connections 2\n"));
+  }
+}
@@ -0,0 +1,97 @@
+use {
+  crate::{
+    log::Err,
+    mycology::{generate::CatInfo, parse},
+    types::Categories,
+  },
+  std::{
+    fs,
+    sync::{Arc, RwLock},
+    time::{Duration, SystemTime},
+  },
+};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A parsed-YAML cache shared across requests, so `generate::get` never has
+/// to re-read or re-parse the YAML file. A background task (spawned alongside `logger` in
+/// `start_server`) is the sole writer; everyone else just takes a read
+/// lock.
+#[derive(Clone)]
+pub struct MycologyCache {
+  data: Arc<RwLock<Categories>>,
+  mtime: Arc<RwLock<Option<SystemTime>>>,
+}
+
+impl MycologyCache {
+  /// Parses `yaml_file` once synchronously, so the cache is warm before the
+  /// first request arrives, then spawns the task that keeps it fresh.
+  pub fn spawn(yaml_file: String) -> MycologyCache {
+    let data = Arc::new(RwLock::new(parse::yaml(&yaml_file)));
+    let mtime = Arc::new(RwLock::new(mtime(&yaml_file)));
+    let cache = MycologyCache {
+      data: data.clone(),
+      mtime: mtime.clone(),
+    };
+
+    tokio::spawn(watch(yaml_file, data, mtime));
+
+    cache
+  }
+
+  /// All categories with their full genus/species tree.
+  pub fn all(&self) -> Categories {
+    self.data.read().unwrap().clone()
+  }
+
+  /// Categories with genera stripped out, for the menu page - this used to
+  /// be a separate, cheaper parse (`Parse::JustCats`); now it's just a
+  /// projection off the same cached tree.
+  pub fn just_cats(&self) -> Categories {
+    self
+      .all()
+      .into_iter()
+      .map(|cat| CatInfo {
+        genera: vec![],
+        ..cat
+      })
+      .collect()
+  }
+
+  /// The YAML file's last modification time, for `Last-Modified`/
+  /// `If-Modified-Since` handling on generated pages.
+  pub fn mtime(&self) -> Option<SystemTime> {
+    *self.mtime.read().unwrap()
+  }
+}
+
+async fn watch(
+  yaml_file: String,
+  data: Arc<RwLock<Categories>>,
+  mtime_cell: Arc<RwLock<Option<SystemTime>>>,
+) {
+  loop {
+    tokio::time::sleep(POLL_INTERVAL).await;
+
+    let modified = match mtime(&yaml_file) {
+      Some(v) => v,
+      None => continue,
+    };
+
+    let stale = mtime_cell.read().unwrap().map_or(true, |prev| modified > prev);
+    if stale {
+      *data.write().unwrap() = parse::yaml(&yaml_file);
+      *mtime_cell.write().unwrap() = Some(modified);
+    }
+  }
+}
+
+fn mtime(yaml_file: &str) -> Option<SystemTime> {
+  match fs::metadata(yaml_file).and_then(|m| m.modified()) {
+    Ok(v) => Some(v),
+    Err(e) => {
+      format!("mycology cache - cannot stat {} - {}", yaml_file, e).log_err();
+      None
+    }
+  }
+}
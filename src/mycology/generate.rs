@@ -1,24 +1,29 @@
 use {
     crate::{
         consts, html,
-        mycology::parse,
+        mycology::cache::MycologyCache,
         server::response::Response,
         types::{Categories, Content, Result},
     },
+    serde::Deserialize,
     std::io,
 };
 
+#[derive(Clone, Deserialize)]
 pub struct CatInfo {
     pub title: String,
     pub label: String,
+    #[serde(default)]
     pub genera: Vec<GenInfo>,
 }
 
+#[derive(Clone, Deserialize)]
 pub struct GenInfo {
     pub title: String,
     pub species: Vec<SpecInfo>,
 }
 
+#[derive(Clone, Deserialize)]
 pub struct SpecInfo {
     pub title: String,
     pub name: String,
@@ -63,10 +68,10 @@ impl FillTemplate for String {
     }
 }
 
-pub fn get(path: &str) -> Result<Response> {
+pub fn get(path: &str, cache: &MycologyCache) -> Result<Response> {
     let requested_category = path.replace('/', "");
     let mime_type = "text/html";
-    let categories = parse::yaml(parse::Parse::JustCats);
+    let categories = cache.just_cats();
     match (
         categories.contains(&requested_category),
         requested_category.is_empty(),
@@ -76,12 +81,14 @@ pub fn get(path: &str) -> Result<Response> {
             mime_type,
             content: html::from_file(consts::PATH.menu)?
                 .fill_menu(categories, &html::from_file(consts::PATH.frag_menu)?),
+            last_modified: cache.mtime(),
         }),
         (true, false) => Ok(Response {
             status: consts::status::HTTP_200,
             mime_type,
             content: html::from_file(consts::PATH.shroompage)?
-                .fill_myc(parse::yaml(parse::Parse::All).filter_data(&requested_category))?,
+                .fill_myc(cache.all().filter_data(&requested_category))?,
+            last_modified: cache.mtime(),
         }),
         _ => Err(Box::new(io::Error::new(
             io::ErrorKind::NotFound,
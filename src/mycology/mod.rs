@@ -0,0 +1,3 @@
+pub mod cache;
+pub mod generate;
+pub mod parse;
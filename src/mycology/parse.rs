@@ -1,141 +1,62 @@
 use {
-  crate::{
-    consts,
-    log::Err,
-    mycology::generate::{CatInfo, GenInfo, SpecInfo},
-    types::{Categories, Genera, Species, YamlChunks},
-  },
+  crate::{log::Err, types::Categories},
   std::fs,
 };
 
-#[derive(Copy, Clone)]
-enum Layer {
-  Category,
-  Genus,
-  Species,
-}
-
-impl Layer {
-  fn condition(&self, s: &str) -> bool {
-    use Layer::*;
-    match self {
-      Category => !s.starts_with("  ") && s.ends_with(':'),
-      // uhh, always false?
-      Genus => s.starts_with("  ") && !s.starts_with("   ") && s.ends_with(':'),
-      Species => s.starts_with("    ") && s.ends_with(':'),
+/// Loads and deserializes `yaml_file` directly into `CatInfo`/`GenInfo`/
+/// `SpecInfo` via serde, so `blurb`/`name`/`title` values round-trip
+/// verbatim no matter what punctuation or indentation they contain. Any
+/// read or parse failure is logged via `log_err` and yields an empty
+/// list, same as before. `MycologyCache::just_cats` projects genera back
+/// off this, so there's no separate "just the categories" mode here.
+pub fn yaml(yaml_file: &str) -> Categories {
+  let raw = match fs::read_to_string(yaml_file) {
+    Ok(v) => v,
+    Err(e) => {
+      format!("yaml read error. :( - {} {}", e, yaml_file).log_err();
+      return vec![];
     }
-  }
-}
-
-pub enum Parse {
-  All,
-  JustCats,
-}
-
-trait Construct {
-  fn struct_category(self, parse_all: Parse) -> Categories;
-  fn struct_genus(self) -> Genera;
-  fn struct_species(self) -> Species;
-}
-
-impl Construct for YamlChunks {
-  fn struct_category(self, parse_all: Parse) -> Categories {
-    self
-      .into_iter()
-      .map(|lines| {
-        let label = lines.first().sanitise();
-        let title = lines
-          .iter()
-          .find(|l| l.trim().starts_with("title:"))
-          .sanitise();
-
-        let genera = match parse_all {
-          Parse::JustCats => vec![],
-          Parse::All => split_by(lines, Layer::Genus).struct_genus(),
-        };
-
-        CatInfo {
-          label,
-          title,
-          genera,
-        }
-      })
-      .collect()
-  }
+  };
 
-  fn struct_genus(self) -> Genera {
-    self
-      .into_iter()
-      .map(|lines| {
-        let title = lines.first().sanitise();
-        let species = split_by(lines, Layer::Species).struct_species();
-        GenInfo { title, species }
-      })
-      .collect()
-  }
-
-  fn struct_species(self) -> Species {
-    self
-      .into_iter()
-      .map(|lines| {
-        let mut species = lines.iter();
-        let title = species.next().sanitise();
-        let name = species.next().sanitise();
-        let blurb = species.map(|s| (Some(s)).sanitise()).collect();
-        SpecInfo { title, name, blurb }
-      })
-      .collect()
-  }
-}
-
-trait Sanitise {
-  fn sanitise(self) -> String;
-}
-
-impl Sanitise for Option<&String> {
-  fn sanitise(self) -> String {
-    self
-      .unwrap_or(&String::new())
-      .trim()
-      .trim_start_matches("blurb: ")
-      .trim_start_matches("common_name: ")
-      .trim_start_matches("title: ")
-      .replace(':', "")
-  }
-}
-
-pub fn yaml(parse_all: Parse) -> Categories {
-  match fs::read_to_string(consts::YAML_FILE) {
-    Ok(v) => {
-      let yaml = v.split('\n').map(str::to_string).collect();
-      let categories = split_by(yaml, Layer::Category);
-      categories.struct_category(parse_all)
-    }
+  match serde_yaml::from_str(&raw) {
+    Ok(v) => v,
     Err(e) => {
-      format!("yaml munching error. :( - {} {}", e, consts::YAML_FILE).log_err();
+      format!("yaml parse error. :( - {} {}", e, yaml_file).log_err();
       vec![]
     }
   }
 }
 
-fn split_by(lines: Vec<String>, layer: Layer) -> YamlChunks {
-  let divisions: Vec<usize> = lines
-    .iter()
-    .enumerate()
-    .filter(|(_, s)| layer.condition(s))
-    .map(|(i, _)| i)
-    .collect();
-  let m_divs = &divisions;
-
-  divisions
-    .iter()
-    .enumerate()
-    .map(|(i, n)| {
-      lines[match m_divs.get(i + 1) {
-        Some(v) => *n..*v,
-        None => *n..lines.len(),
-      }]
-      .to_vec()
-    })
-    .collect()
+#[cfg(test)]
+mod tests {
+  use crate::types::Categories;
+
+  // The hand-rolled indentation parser this replaced would either choke on
+  // a colon inside a value or silently truncate indentation past its
+  // hardcoded 3-layer assumption. serde_yaml has neither limitation, but
+  // that's exactly the kind of regression a refactor like this can
+  // reintroduce unnoticed, so it's worth pinning down with a round-trip.
+  #[test]
+  fn round_trips_colons_and_deep_indentation() {
+    let raw = "
+- title: Gilled Mushrooms
+  label: gilled
+  genera:
+        - title: Amanita
+          species:
+                - title: Fly Agaric
+                  name: Amanita muscaria
+                  blurb: \"Recognizable by: a red cap with white spots.\"
+";
+
+    let categories: Categories = serde_yaml::from_str(raw).unwrap();
+
+    assert_eq!(categories.len(), 1);
+    assert_eq!(categories[0].genera.len(), 1);
+    assert_eq!(categories[0].genera[0].species.len(), 1);
+    assert_eq!(
+      categories[0].genera[0].species[0].blurb,
+      "Recognizable by: a red cap with white spots."
+    );
+  }
 }
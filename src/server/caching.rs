@@ -0,0 +1,68 @@
+use std::time::SystemTime;
+
+/// Parses an `If-Modified-Since` header as an RFC 1123 HTTP-date (e.g.
+/// `Sun, 06 Nov 1994 08:49:37 GMT`). Any value that doesn't parse is
+/// treated the same as a missing header - "always send the full body" -
+/// rather than an error, since this is only a caching optimization.
+pub fn parse_if_modified_since(header: &str) -> Option<SystemTime> {
+  httpdate::parse_http_date(header).ok()
+}
+
+/// Formats a `Last-Modified` header value.
+pub fn format_last_modified(mtime: SystemTime) -> String {
+  httpdate::fmt_http_date(mtime)
+}
+
+/// True when the resource is no newer than the client's cached copy, i.e.
+/// a `304 Not Modified` can be served instead of the full body.
+pub fn not_modified(resource_mtime: Option<SystemTime>, if_modified_since: Option<SystemTime>) -> bool {
+  match (resource_mtime, if_modified_since) {
+    (Some(resource), Some(client)) => {
+      // HTTP-dates carry whole seconds only, so compare at that precision
+      let secs = |t: SystemTime| {
+        t.duration_since(SystemTime::UNIX_EPOCH)
+          .map(|d| d.as_secs())
+          .unwrap_or(0)
+      };
+      secs(resource) <= secs(client)
+    }
+    _ => false,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::time::Duration;
+
+  #[test]
+  fn parses_a_well_formed_http_date() {
+    let parsed = parse_if_modified_since("Sun, 06 Nov 1994 08:49:37 GMT");
+    assert_eq!(parsed, Some(httpdate::parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT").unwrap()));
+  }
+
+  #[test]
+  fn tolerates_a_garbage_header() {
+    assert_eq!(parse_if_modified_since("not a date"), None);
+  }
+
+  #[test]
+  fn not_modified_when_resource_is_no_newer() {
+    let mtime = SystemTime::UNIX_EPOCH + Duration::from_secs(1000);
+    let client = SystemTime::UNIX_EPOCH + Duration::from_secs(1000);
+    assert!(not_modified(Some(mtime), Some(client)));
+  }
+
+  #[test]
+  fn modified_when_resource_is_newer_than_client_copy() {
+    let mtime = SystemTime::UNIX_EPOCH + Duration::from_secs(2000);
+    let client = SystemTime::UNIX_EPOCH + Duration::from_secs(1000);
+    assert!(!not_modified(Some(mtime), Some(client)));
+  }
+
+  #[test]
+  fn not_modified_is_false_without_both_timestamps() {
+    assert!(!not_modified(None, None));
+    assert!(!not_modified(Some(SystemTime::UNIX_EPOCH), None));
+  }
+}
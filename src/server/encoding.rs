@@ -0,0 +1,131 @@
+use {
+  crate::types::Content,
+  std::io::Write,
+};
+
+/// Below this size, compressing isn't worth the CPU - the gzip/brotli
+/// container overhead eats whatever the algorithm would have saved.
+const COMPRESS_THRESHOLD: usize = 1024;
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum Codec {
+  Gzip,
+  Brotli,
+}
+
+impl Codec {
+  fn name(&self) -> &'static str {
+    match self {
+      Codec::Gzip => "gzip",
+      Codec::Brotli => "br",
+    }
+  }
+}
+
+/// Parses an `Accept-Encoding` header into the client's supported codec,
+/// per its stated preference (`q=` weights, highest first, `q=0` entries
+/// excluded), falling back to `None` (send identity) when nothing matches.
+pub fn negotiate(accept_encoding: Option<&str>) -> Option<Codec> {
+  let header = accept_encoding?;
+
+  let mut preferences: Vec<(&str, f32)> = header
+    .split(',')
+    .filter_map(|entry| {
+      let entry = entry.trim();
+      let mut parts = entry.split(';');
+      let name = parts.next()?.trim();
+
+      let q = parts
+        .find_map(|param| param.trim().strip_prefix("q="))
+        .and_then(|v| v.trim().parse::<f32>().ok())
+        .unwrap_or(1.0);
+
+      (q > 0.0).then_some((name, q))
+    })
+    .collect();
+
+  // stable sort keeps the header's own ordering as the tiebreaker among
+  // equal q values
+  preferences.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap());
+
+  preferences.into_iter().find_map(|(name, _)| match name {
+    "br" => Some(Codec::Brotli),
+    "gzip" => Some(Codec::Gzip),
+    _ => None,
+  })
+}
+
+/// Textual mime types are the only ones worth compressing - images, fonts,
+/// and the like are already compressed in their own format.
+pub fn is_textual(mime_type: &str) -> bool {
+  mime_type.starts_with("text/") || mime_type == "application/json"
+}
+
+/// Compresses `content` with `codec` when it's large enough to be worth
+/// it, returning the (possibly unchanged) body alongside the codec that
+/// was actually applied, if any.
+pub fn compress(content: Content, codec: Codec) -> (Content, Option<&'static str>) {
+  if content.len() < COMPRESS_THRESHOLD {
+    return (content, None);
+  }
+
+  let compressed = match codec {
+    Codec::Gzip => {
+      let mut encoder =
+        flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+      encoder.write_all(&content).and_then(|_| encoder.finish())
+    }
+    Codec::Brotli => (|| {
+      let mut out = Vec::new();
+      let mut writer = brotli::CompressorWriter::new(&mut out, 4096, 5, 22);
+      writer.write_all(&content)?;
+      writer.flush()?;
+      drop(writer);
+      Ok(out)
+    })(),
+  };
+
+  match compressed {
+    Ok(bytes) => (bytes, Some(codec.name())),
+    Err(_) => (content, None),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn negotiate_picks_highest_q_weight() {
+    let picked = negotiate(Some("gzip;q=0.5, br;q=0.8"));
+    assert!(matches!(picked, Some(Codec::Brotli)));
+  }
+
+  #[test]
+  fn negotiate_excludes_q_zero() {
+    let picked = negotiate(Some("br;q=0, gzip"));
+    assert!(matches!(picked, Some(Codec::Gzip)));
+  }
+
+  #[test]
+  fn negotiate_falls_back_to_none_on_no_match() {
+    assert!(negotiate(Some("deflate, identity")).is_none());
+    assert!(negotiate(None).is_none());
+  }
+
+  #[test]
+  fn compress_leaves_small_content_untouched() {
+    let content = vec![b'x'; COMPRESS_THRESHOLD - 1];
+    let (out, encoding) = compress(content.clone(), Codec::Gzip);
+    assert_eq!(out, content);
+    assert!(encoding.is_none());
+  }
+
+  #[test]
+  fn compress_applies_codec_above_threshold() {
+    let content = vec![b'x'; COMPRESS_THRESHOLD + 1];
+    let (out, encoding) = compress(content.clone(), Codec::Gzip);
+    assert_eq!(encoding, Some("gzip"));
+    assert_ne!(out, content);
+  }
+}
@@ -0,0 +1,28 @@
+use crate::{consts, log::Err, server::response::Response, types::Result};
+
+pub fn nf404() -> Result<Response> {
+  Ok(Response {
+    status: consts::status::HTTP_404,
+    mime_type: "text/plain",
+    content: b"404 Not Found".to_vec(),
+    last_modified: None,
+  })
+}
+
+/// Turns a failed `Response` lookup into a proper 404 page instead of
+/// letting the error abort the connection.
+pub trait ReplaceErr {
+  fn replace_err(self) -> Result<Response>;
+}
+
+impl ReplaceErr for Result<Response> {
+  fn replace_err(self) -> Result<Response> {
+    match self {
+      Ok(r) => Ok(r),
+      Err(e) => {
+        e.log_err();
+        nf404()
+      }
+    }
+  }
+}
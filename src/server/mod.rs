@@ -0,0 +1,6 @@
+pub mod caching;
+pub mod encoding;
+pub mod err;
+pub mod request;
+pub mod response;
+pub mod run;
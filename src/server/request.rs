@@ -0,0 +1,80 @@
+use {
+  crate::{
+    config::Config,
+    server::response::Host,
+    types::{IpAddr, Result},
+  },
+  tokio::io::AsyncBufReadExt,
+};
+
+pub struct RequestInfo {
+  pub host: Option<Host>,
+  pub path: Option<String>,
+  pub user_agent: Option<String>,
+  pub ip: Option<IpAddr>,
+  pub referer: Option<String>,
+  pub accept_encoding: Option<String>,
+  pub if_modified_since: Option<String>,
+}
+
+/// Reads the request line and headers off `reader`, resolving the `Host:`
+/// header to a `Host` via `config`'s `[[hosts]]` table rather than a
+/// hardcoded domain match.
+pub async fn parse<R>(mut reader: R, config: &Config) -> Result<RequestInfo>
+where
+  R: AsyncBufReadExt + Unpin,
+{
+  let mut path = None;
+  let mut domain = None;
+  let mut user_agent = None;
+  let mut referer = None;
+  let mut accept_encoding = None;
+  let mut if_modified_since = None;
+
+  let mut line = String::new();
+  let mut first_line = true;
+
+  loop {
+    line.clear();
+    if reader.read_line(&mut line).await? == 0 {
+      break;
+    }
+
+    let trimmed = line.trim_end_matches(['\r', '\n']);
+    if trimmed.is_empty() {
+      break;
+    }
+
+    if first_line {
+      first_line = false;
+      path = trimmed.split_whitespace().nth(1).map(str::to_string);
+      continue;
+    }
+
+    let Some((header, value)) = trimmed.split_once(':') else {
+      continue;
+    };
+    let value = value.trim().to_string();
+
+    match header.to_ascii_lowercase().as_str() {
+      "host" => domain = Some(value),
+      "user-agent" => user_agent = Some(value),
+      "referer" => referer = Some(value),
+      "accept-encoding" => accept_encoding = Some(value),
+      "if-modified-since" => if_modified_since = Some(value),
+      _ => {}
+    }
+  }
+
+  let host = domain.as_deref().and_then(|d| config.host_for_domain(d));
+
+  Ok(RequestInfo {
+    host,
+    path,
+    user_agent,
+    ip: None,
+    referer,
+    accept_encoding,
+    if_modified_since,
+  })
+}
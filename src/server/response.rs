@@ -0,0 +1,45 @@
+use {
+  crate::{
+    consts, html,
+    metrics::Metrics,
+    types::{Content, Result},
+  },
+  std::{fs, time::SystemTime},
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Host {
+  Mycology,
+  Site,
+}
+
+pub struct Response {
+  pub status: &'static str,
+  pub mime_type: &'static str,
+  pub content: Content,
+  pub last_modified: Option<SystemTime>,
+}
+
+pub fn get(path: &str, metrics: &Metrics, uptime: SystemTime) -> Result<Response> {
+  if path == "/metrics" {
+    return Ok(crate::metrics::get(metrics, uptime));
+  }
+
+  let file_path = site_file_path(path);
+  let content = html::from_file(&file_path)?.into_bytes();
+  let last_modified = fs::metadata(&file_path).and_then(|m| m.modified()).ok();
+
+  Ok(Response {
+    status: consts::status::HTTP_200,
+    mime_type: "text/html",
+    content,
+    last_modified,
+  })
+}
+
+fn site_file_path(path: &str) -> String {
+  match path {
+    "" | "/" => format!("{}/index.html", consts::PATH.site_root),
+    _ => format!("{}{}", consts::PATH.site_root, path),
+  }
+}
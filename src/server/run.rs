@@ -1,8 +1,13 @@
 use {
   crate::{
+    config::Config,
+    consts,
     log::*,
+    metrics::Metrics,
     mycology,
     server::{
+      caching,
+      encoding::{self, Codec},
       request::*,
       response::{self, *},
     },
@@ -19,13 +24,25 @@ use {
   },
 };
 
-pub async fn start_server() -> Result<()> {
+pub async fn start_server(config: Config) -> Result<()> {
   use futures::stream::StreamExt as _;
 
-  let (log_send, log_recv) = make_tube();
-  let mut logger = pin!(logger(log_recv));
+  let config = Arc::new(config);
+  set_log_path(config.log_file.clone());
+
+  let mycology_cache = mycology::cache::MycologyCache::spawn(config.yaml_file.clone());
+  let metrics = Arc::new(Metrics::default());
 
-  let listener = TcpListener::bind("127.0.0.1:7878").await?;
+  let (log_send, log_recv) = make_tube();
+  let mut logger = pin!(logger(
+    log_recv,
+    config.log_file.clone(),
+    config.log_format,
+    config.log_rotate_bytes,
+    metrics.clone(),
+  ));
+
+  let listener = TcpListener::bind(&config.bind_addr).await?;
   let mut futures = futures::stream::FuturesUnordered::new();
 
   let uptime = time::SystemTime::now();
@@ -45,8 +62,11 @@ pub async fn start_server() -> Result<()> {
       result = listener.accept() => {
         let (stream, _) = result?;
         let log_send = log_send.clone();
+        let config = config.clone();
+        let mycology_cache = mycology_cache.clone();
+        let metrics = metrics.clone();
 
-        futures.push(handle_connection(stream, uptime, log_send));
+        futures.push(handle_connection(stream, uptime, log_send, config, mycology_cache, metrics));
       },
 
       res = futures.next() => {
@@ -55,13 +75,16 @@ pub async fn start_server() -> Result<()> {
         }
       },
     }
-  } 
+  }
 }
 
 async fn handle_connection(
   mut stream: TcpStream,
   uptime: time::SystemTime,
   log_send: SendTube<Log>,
+  config: Arc<Config>,
+  mycology_cache: mycology::cache::MycologyCache,
+  metrics: Arc<Metrics>,
 ) -> Result<()> {
   let cxn_time = time::SystemTime::now();
 
@@ -71,18 +94,31 @@ async fn handle_connection(
     user_agent,
     ip,
     referer,
-  } = parse(BufReader::new(&mut stream)).await?;
+    accept_encoding,
+    if_modified_since,
+  } = parse(BufReader::new(&mut stream), &config).await?;
 
   let response = if let (Some(domain), Some(path)) = (&host, &path) {
     match domain {
-      Host::Mycology => mycology::generate::get(path),
-      Host::Site => response::get(path),
+      Host::Mycology => mycology::generate::get(path, &mycology_cache),
+      Host::Site => response::get(path, &metrics, uptime),
     }
     .replace_err()
   } else {
     err::nf404()
   }?;
 
+  let if_modified_since = if_modified_since.as_deref().and_then(caching::parse_if_modified_since);
+  let response = if caching::not_modified(response.last_modified, if_modified_since) {
+    Response {
+      status: consts::status::HTTP_304,
+      content: vec![],
+      ..response
+    }
+  } else {
+    response
+  };
+
   let status = response
     .status
     .split_whitespace()
@@ -90,9 +126,11 @@ async fn handle_connection(
       true => a,
       false => format!("{a} {b}"),
     });
-  let length = response.content.len();
 
-  stream.write_all(&response.prepend_headers()).await?;
+  let codec = encoding::negotiate(accept_encoding.as_deref());
+  let (body, length) = response.prepend_headers(codec);
+
+  stream.write_all(&body).await?;
   stream.flush().await?;
 
   log_send
@@ -114,22 +152,46 @@ async fn handle_connection(
 }
 
 trait Prepend {
-  fn prepend_headers(self) -> Content;
+  /// Builds the full response (headers + body), compressing the body
+  /// with `codec` first when it's a textual mime type and at least
+  /// `encoding::compress`'s size threshold. Returns the bytes
+  /// to write alongside the body length actually sent, so the caller can
+  /// record the post-compression size in the log.
+  fn prepend_headers(self, codec: Option<Codec>) -> (Content, usize);
 }
 
 impl Prepend for Response {
-  fn prepend_headers(self) -> Content {
-    [
+  fn prepend_headers(self, codec: Option<Codec>) -> (Content, usize) {
+    let Response {
+      status,
+      mime_type,
+      content,
+      last_modified,
+    } = self;
+
+    let (content, content_encoding) = match codec.filter(|_| encoding::is_textual(mime_type)) {
+      Some(codec) => encoding::compress(content, codec),
+      None => (content, None),
+    };
+    let length = content.len();
+
+    let encoding_header = content_encoding
+      .map(|enc| format!("Content-Encoding: {enc}\r\n"))
+      .unwrap_or_default();
+    let last_modified_header = last_modified
+      .map(|mtime| format!("Last-Modified: {}\r\n", caching::format_last_modified(mtime)))
+      .unwrap_or_default();
+
+    let body = [
       format!(
-        "{}\r\nContent-Length: {}\r\nContent-Type: {}\r\n\r\n",
-        self.status,
-        self.content.len(),
-        self.mime_type
+        "{status}\r\nContent-Length: {length}\r\nContent-Type: {mime_type}\r\n{encoding_header}{last_modified_header}\r\n",
       )
       .into_bytes(),
-      self.content,
+      content,
     ]
-    .concat()
+    .concat();
+
+    (body, length)
   }
 }
 
@@ -0,0 +1,20 @@
+use std::{
+  error,
+  sync::{Arc, Mutex},
+};
+
+pub type IpAddr = [u8; 4];
+pub type Content = Vec<u8>;
+pub type Result<T> = std::result::Result<T, Box<dyn error::Error>>;
+
+pub type Categories = Vec<crate::mycology::generate::CatInfo>;
+pub type Genera = Vec<crate::mycology::generate::GenInfo>;
+pub type Species = Vec<crate::mycology::generate::SpecInfo>;
+
+pub mod tubes {
+  use super::*;
+  use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+
+  pub type SendTube<T> = Arc<Mutex<UnboundedSender<T>>>;
+  pub type Tubes<T> = (SendTube<T>, UnboundedReceiver<T>);
+}